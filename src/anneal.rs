@@ -0,0 +1,74 @@
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{Maze, Neighbours, Point};
+
+const START_TEMPERATURE: f64 = 10.0;
+const END_TEMPERATURE: f64 = 0.1;
+
+impl Maze {
+    /// Optimizes the maze for a custom scalar objective using simulated annealing.
+    ///
+    /// Starting from `self`, repeatedly carves or fills a single interior
+    /// cell, re-checking via [`Maze::solve`] that the maze stays connected
+    /// (after a fill, every open neighbour still reaches an exit; after a
+    /// carve, the carved cell does) before accepting the move. Worsening moves are
+    /// still accepted with probability `exp((new - old) / temperature)`,
+    /// where the temperature is annealed geometrically over `time_limit`.
+    /// Returns the best-scoring maze seen.
+    pub fn optimize(&self, seed: u64, score: impl Fn(&Maze) -> i64, time_limit: Duration) -> Maze {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let start = Instant::now();
+
+        let mut current = self.clone();
+        let mut current_score = score(&current);
+        let mut best = current.clone();
+        let mut best_score = current_score;
+
+        // No interior cell exists to carve or fill; nothing to optimize.
+        if current.n <= 2 || current.m <= 2 { return best; }
+
+        while start.elapsed() < time_limit {
+            let fraction = start.elapsed().as_secs_f64() / time_limit.as_secs_f64();
+            let temperature = START_TEMPERATURE * (END_TEMPERATURE / START_TEMPERATURE).powf(fraction);
+
+            let point = (rng.gen_range(1..current.n - 1), rng.gen_range(1..current.m - 1));
+            current[point] = !current[point];
+
+            if !current.stays_connected(point) {
+                current[point] = !current[point];
+                continue;
+            }
+
+            let next_score = score(&current);
+            let delta = (next_score - current_score) as f64;
+
+            if delta >= 0.0 || rng.gen::<f64>() < (delta / temperature).exp() {
+                current_score = next_score;
+                if current_score > best_score {
+                    best_score = current_score;
+                    best = current.clone();
+                }
+            } else {
+                current[point] = !current[point];
+            }
+        }
+
+        best
+    }
+
+    /// Checks that a move at `point` keeps the maze fully connected: after a
+    /// fill, every still-open neighbour must still reach an exit; after a
+    /// carve, `point` itself must be able to reach one (otherwise it's an
+    /// unreachable island, since nothing led to it before).
+    fn stays_connected(&self, point: Point) -> bool {
+        if self[point] {
+            Neighbours::of(point)
+                .filter(|&next| self.is_valid(next))
+                .all(|next| self[next] || self.solve(next).is_some())
+        } else {
+            self.solve(point).is_some()
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// Disjoint-set (union-find) structure with path compression and union by size.
+///
+/// Each entry holds either a non-negative parent index, or, for a set root,
+/// the negated size of its set.
+pub(crate) struct Dsu {
+    parent: Vec<i32>,
+}
+
+impl Dsu {
+    pub fn new(n: usize) -> Self {
+        Self { parent: vec![-1; n] }
+    }
+
+    /// Returns the representative of `x`'s set, compressing the path along the way.
+    pub fn root(&mut self, x: usize) -> usize {
+        if self.parent[x] < 0 {
+            x
+        } else {
+            let root = self.root(self.parent[x] as usize);
+            self.parent[x] = root as i32;
+            root
+        }
+    }
+
+    /// Returns whether `x` and `y` already belong to the same set.
+    pub fn find(&mut self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// Merges the sets containing `x` and `y`, attaching the smaller to the larger.
+    ///
+    /// Returns `false` if they were already in the same set.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.root(x), self.root(y));
+        if x == y { return false; }
+
+        if -self.parent[x] < -self.parent[y] { std::mem::swap(&mut x, &mut y); }
+        self.parent[x] += self.parent[y];
+        self.parent[y] = x as i32;
+
+        true
+    }
+}
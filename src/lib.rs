@@ -1,17 +1,28 @@
 use std::{
-    collections::VecDeque,
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
     ops::{Index, IndexMut},
     fmt::{self, Display, Formatter},
 };
 use rand::{Rng, thread_rng};
 
+use dsu::Dsu;
+
+mod anneal;
+mod dsu;
+mod reach;
+mod rich;
 #[cfg(test)]
 mod tests;
 
+pub use rich::{Cell, RichMaze};
+
 #[inline]
 fn safe_add(u: usize, i: i64) -> usize {
     if i.is_negative() {
-        u - i.wrapping_abs() as usize as usize
+        let i = i.wrapping_abs() as usize;
+        // Saturate instead of underflowing; `is_valid` rejects the sentinel.
+        if i > u { usize::MAX } else { u - i }
     } else {
         u + i as usize
     }
@@ -20,7 +31,7 @@ fn safe_add(u: usize, i: i64) -> usize {
 pub type Point = (usize, usize);
 pub type Path = Vec<Point>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Matrix<T> {
     data: Vec<T>,
     n: usize,
@@ -87,6 +98,99 @@ impl Iterator for Neighbours {
     }
 }
 
+/// A cell that can tell whether it blocks movement.
+///
+/// Implementing this for a custom cell type lets [`Matrix::solve`] and the
+/// rest of the core search loop run over it without forking any of the logic.
+pub trait Passable {
+    fn is_wall(&self) -> bool;
+}
+
+impl Passable for bool {
+    fn is_wall(&self) -> bool { *self }
+}
+
+impl Passable for u32 {
+    fn is_wall(&self) -> bool { *self == u32::MAX }
+}
+
+/// A partially-known cell, for mazes that are being discovered as they're explored.
+///
+/// Only `Wall` blocks movement; `Undefined` and `Unknown` are treated as open
+/// until proven otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BW {
+    #[default]
+    Undefined,
+    Open,
+    Wall,
+    Unknown,
+}
+
+impl Passable for BW {
+    fn is_wall(&self) -> bool {
+        *self == BW::Wall
+    }
+}
+
+impl<C: Passable> Matrix<C> {
+    /// Bounds check.
+    fn is_valid(&self, point: Point) -> bool {
+        point.0 < self.n && point.1 < self.m
+    }
+
+    fn is_exit(&self, point: Point) -> bool {
+        point.0 == 0 || point.0 == self.n - 1
+            || point.1 == 0 || point.1 == self.m - 1
+    }
+
+    /// Returns a path from the `start` point to an exit, if one exists.
+    pub fn solve(&self, start: Point) -> Option<Path> {
+        if self[start].is_wall() { return None; }
+
+        let mut queue = VecDeque::new();
+        let mut costs = Matrix::<usize>::new(self.n, self.m);
+        let mut exit = None;
+
+        costs[start] = 1;
+        queue.push_back(start);
+
+        while let Some(current) = queue.pop_front() {
+            if exit.is_some() { break; }
+            for next in Neighbours::of(current) {
+                if !self.is_valid(next) || self[next].is_wall() || costs[next] != 0 { continue; }
+                if self.is_exit(next) { exit = Some(next); }
+                costs[next] = costs[current] + 1;
+                queue.push_back(next);
+            }
+        }
+
+        // Restore a path.
+        let mut current = if let Some(point) = exit { point } else { return None; };
+        let mut path = vec![current];
+
+        while current != start {
+            for next in Neighbours::of(current) {
+                if !self.is_valid(next) { continue; }
+
+                if costs[next] != 0 && costs[next] < costs[current] {
+                    current = next;
+                    path.push(current);
+                    break;
+                }
+            }
+        }
+
+        // Change direction.
+        let path = path.iter()
+            .rev()
+            .cloned()
+            .collect();
+
+        Some(path)
+    }
+}
+
 pub type Maze = Matrix<bool>;
 
 impl Maze {
@@ -129,46 +233,150 @@ impl Maze {
         maze
     }
 
-    fn is_exit(&self, point: Point) -> bool {
-        point.0 == 0 || point.0 == self.n - 1
-            || point.1 == 0 || point.1 == self.m - 1
+    /// Generates a maze using Kruskal's algorithm over a disjoint-set structure.
+    ///
+    /// Cells live on odd coordinates, with even coordinates holding the walls
+    /// between them. Unlike [`Maze::generate`], this always yields a uniform
+    /// spanning tree: no loops, and every cell connected to every other.
+    pub fn generate_kruskal(n: usize, m: usize) -> Self {
+        let mut maze = Self { data: vec![true; n * m], n, m };
+        let mut rng = thread_rng();
+
+        let rows = (n - 1) / 2;
+        let cols = (m - 1) / 2;
+
+        // Too small to hold an interior cell/wall grid; nothing to carve.
+        if rows == 0 || cols == 0 {
+            maze.data.iter_mut().for_each(|wall| *wall = false);
+            return maze;
+        }
+
+        // Carve every cell open; only the walls between them remain undecided.
+        for i in 0..rows {
+            for j in 0..cols {
+                maze[(2 * i + 1, 2 * j + 1)] = false;
+            }
+        }
+
+        // Collect every wall segment that separates two adjacent cells.
+        let mut walls = Vec::new();
+        for i in 0..rows {
+            for j in 0..cols {
+                let cell = i * cols + j;
+                if j + 1 < cols { walls.push((cell, cell + 1, (2 * i + 1, 2 * j + 2))); }
+                if i + 1 < rows { walls.push((cell, cell + cols, (2 * i + 2, 2 * j + 1))); }
+            }
+        }
+
+        let mut dsu = Dsu::new(rows * cols);
+        while let Some((a, b, wall)) = walls.remove_random(&mut rng) {
+            if !dsu.find(a, b) {
+                dsu.union(a, b);
+                maze[wall] = false;
+            }
+        }
+
+        // Finally, open a single exit on the border.
+        let i = rng.gen_range(0..rows);
+        let j = rng.gen_range(0..cols);
+        if rng.gen::<bool>() {
+            maze[(2 * i + 1, 0)] = false;
+        } else {
+            maze[(0, 2 * j + 1)] = false;
+        }
+
+        maze
     }
 
-    /// Bounds check.
-    fn is_valid(&self, point: Point) -> bool {
-        point.0 < self.n && point.1 < self.m
+    /// Returns the cheapest path and its total cost from `start` to an exit,
+    /// using Dijkstra's algorithm over a weighted cost grid.
+    ///
+    /// `costs` gives the price of entering each cell; `u32::MAX` marks a wall.
+    pub fn solve_weighted(&self, start: Point, costs: &Matrix<u32>) -> Option<(Path, u32)> {
+        if costs[start] == u32::MAX { return None; }
+
+        let mut dist = Matrix::<u32>::new(self.n, self.m);
+        for cost in dist.data.iter_mut() { *cost = u32::MAX; }
+        dist[start] = 0;
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((0u32, start)));
+
+        while let Some(Reverse((cost, current))) = heap.pop() {
+            if cost > dist[current] { continue; }
+            for next in Neighbours::of(current) {
+                if !self.is_valid(next) || costs[next] == u32::MAX { continue; }
+                let next_cost = cost + costs[next];
+                if next_cost < dist[next] {
+                    dist[next] = next_cost;
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        self.reconstruct_weighted(start, costs, &dist)
     }
 
-    /// Returns a path from the `start` point to an exit, if exists.
-    pub fn solve(&self, start: Point) -> Option<Path> {
-        if self[start] { return None; }
+    /// Returns the cheapest path and its total cost from `start` to an exit,
+    /// using A* with a Manhattan-distance heuristic to the nearest border.
+    ///
+    /// The result is the cheapest only when every open cell costs at least 1.
+    pub fn solve_astar(&self, start: Point, costs: &Matrix<u32>) -> Option<(Path, u32)> {
+        if costs[start] == u32::MAX { return None; }
 
-        let mut queue = VecDeque::new();
-        let mut costs = Matrix::<usize>::new(self.n, self.m);
-        let mut exit = None;
+        let mut dist = Matrix::<u32>::new(self.n, self.m);
+        for cost in dist.data.iter_mut() { *cost = u32::MAX; }
+        dist[start] = 0;
 
-        costs[start] = 1;
-        queue.push_back(start);
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((self.heuristic(start), start)));
 
-        while let Some(current) = queue.pop_front() {
-            if exit.is_some() { break; }
+        while let Some(Reverse((_, current))) = heap.pop() {
+            if self.is_exit(current) { break; }
             for next in Neighbours::of(current) {
-                if !self.is_valid(next) || self[next] || costs[next] != 0 { continue; }
-                if self.is_exit(next) { exit = Some(next); }
-                costs[next] = costs[current] + 1;
-                queue.push_back(next);
+                if !self.is_valid(next) || costs[next] == u32::MAX { continue; }
+                let next_dist = dist[current] + costs[next];
+                if next_dist < dist[next] {
+                    dist[next] = next_dist;
+                    heap.push(Reverse((next_dist + self.heuristic(next), next)));
+                }
             }
         }
 
-        // Restore a path.
-        let mut current = if let Some(point) = exit { point } else { return None; };
+        self.reconstruct_weighted(start, costs, &dist)
+    }
+
+    /// Distance from `point` to the nearest of the four borders, used as a
+    /// heuristic for the distance to the nearest exit; it is admissible only
+    /// when every open cell costs at least 1.
+    fn heuristic(&self, point: Point) -> u32 {
+        let (i, j) = point;
+        let di = i.min(self.n - 1 - i);
+        let dj = j.min(self.m - 1 - j);
+        di.min(dj) as u32
+    }
+
+    /// Picks the cheapest reachable exit and walks `dist` back down to `start`.
+    fn reconstruct_weighted(&self, start: Point, costs: &Matrix<u32>, dist: &Matrix<u32>) -> Option<(Path, u32)> {
+        let mut exit = None;
+        for i in 0..self.n {
+            for j in 0..self.m {
+                let point = (i, j);
+                if !self.is_exit(point) || dist[point] == u32::MAX { continue; }
+                if exit.is_none_or(|(_, cost)| dist[point] < cost) {
+                    exit = Some((point, dist[point]));
+                }
+            }
+        }
+
+        let (mut current, cost) = exit?;
         let mut path = vec![current];
 
         while current != start {
             for next in Neighbours::of(current) {
                 if !self.is_valid(next) { continue; }
-
-                if costs[next] != 0 && costs[next] < costs[current] {
+                // Only a neighbour that `current` was actually relaxed from.
+                if dist[next] < dist[current] && dist[next] == dist[current] - costs[current] {
                     current = next;
                     path.push(current);
                     break;
@@ -176,13 +384,9 @@ impl Maze {
             }
         }
 
-        // Change direction.
-        let path = path.iter()
-            .rev()
-            .cloned()
-            .collect();
+        path.reverse();
 
-        Some(path)
+        Some((path, cost))
     }
 }
 
@@ -0,0 +1,48 @@
+use std::collections::HashSet;
+
+use rayon::prelude::*;
+
+use crate::{dsu::Dsu, Maze, Matrix, Neighbours};
+
+impl Maze {
+    /// Computes, for every cell, whether it can reach an exit.
+    ///
+    /// Rather than running [`Maze::solve`] once per cell, groups open cells
+    /// into connected components with a union-find, then marks whole
+    /// components reachable at once if they contain an open border cell —
+    /// with the final per-cell pass run in parallel over rayon.
+    pub fn reachability(&self) -> Matrix<bool> {
+        let mut dsu = Dsu::new(self.n * self.m);
+
+        for i in 0..self.n {
+            for j in 0..self.m {
+                if self[(i, j)] { continue; }
+                for next in Neighbours::of((i, j)) {
+                    if !self.is_valid(next) || self[next] { continue; }
+                    dsu.union(i * self.m + j, next.0 * self.m + next.1);
+                }
+            }
+        }
+
+        let roots: Vec<usize> = (0..self.n * self.m).map(|index| dsu.root(index)).collect();
+
+        let exits: HashSet<usize> = (0..self.n)
+            .flat_map(|i| (0..self.m).map(move |j| (i, j)))
+            .filter(|&point| !self[point] && self.is_exit(point))
+            .map(|(i, j)| roots[i * self.m + j])
+            .collect();
+
+        let data = roots.par_iter()
+            .enumerate()
+            .map(|(index, root)| !self.data[index] && exits.contains(root))
+            .collect();
+
+        Matrix { data, n: self.n, m: self.m }
+    }
+
+    /// Returns whether every open cell in the maze can reach an exit.
+    pub fn is_perfect(&self) -> bool {
+        let reachable = self.reachability();
+        (0..self.n * self.m).all(|index| self.data[index] || reachable.data[index])
+    }
+}
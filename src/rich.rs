@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{Matrix, Neighbours, Path, Point};
+
+/// A cell in a [`RichMaze`].
+///
+/// `Key` and `Door` carry an index in `0..26`, mapping `a..=z` to a matching
+/// `A..=Z`: a door is only passable once its key has been collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Cell {
+    Open,
+    #[default]
+    Wall,
+    Key(u8),
+    Door(u8),
+}
+
+pub type RichMaze = Matrix<Cell>;
+
+impl RichMaze {
+    /// Bounds check.
+    fn is_valid(&self, point: Point) -> bool {
+        point.0 < self.n && point.1 < self.m
+    }
+
+    /// Returns the minimum number of steps needed to collect every key in the
+    /// maze starting from `start`, together with the path that achieves it.
+    ///
+    /// Searches the product state space of `(Point, key_bitmask)`: a door is
+    /// passable only once the matching key bit is set, and stepping onto a
+    /// key ORs its bit into the mask.
+    pub fn collect_all_keys(&self, start: Point) -> Option<(usize, Path)> {
+        if matches!(self[start], Cell::Wall | Cell::Door(_)) { return None; }
+
+        let mut full_mask = 0u32;
+        for cell in self.data.iter() {
+            if let Cell::Key(key) = cell { full_mask |= 1 << key; }
+        }
+
+        // A key at `start` itself is already collected before the first step.
+        let start_mask = match self[start] {
+            Cell::Key(key) => 1 << key,
+            _ => 0u32,
+        };
+
+        let mut visited = HashSet::new();
+        let mut parent = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        visited.insert((start, start_mask));
+        queue.push_back((start, start_mask, 0usize));
+
+        while let Some((point, mask, steps)) = queue.pop_front() {
+            if mask == full_mask {
+                return Some((steps, self.reconstruct(point, mask, &parent)));
+            }
+            for next in Neighbours::of(point) {
+                if !self.is_valid(next) { continue; }
+                let next_mask = match self[next] {
+                    Cell::Wall => continue,
+                    Cell::Door(door) if mask & (1 << door) == 0 => continue,
+                    Cell::Key(key) => mask | (1 << key),
+                    Cell::Open | Cell::Door(_) => mask,
+                };
+
+                let state = (next, next_mask);
+                if visited.insert(state) {
+                    parent.insert(state, (point, mask));
+                    queue.push_back((next, next_mask, steps + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Walks the `parent` map back from `(point, mask)` down to the start.
+    fn reconstruct(
+        &self,
+        mut point: Point,
+        mut mask: u32,
+        parent: &HashMap<(Point, u32), (Point, u32)>,
+    ) -> Path {
+        let mut path = vec![point];
+
+        while let Some(&(prev_point, prev_mask)) = parent.get(&(point, mask)) {
+            point = prev_point;
+            mask = prev_mask;
+            path.push(point);
+        }
+
+        path.reverse();
+
+        path
+    }
+}
@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use crate::*;
 
-#[test]
-fn test_simple_maze() {
+/// The 5x5 maze shared by the tests below: a single winding corridor from
+/// `(3, 1)` up to the exit at `(0, 1)`.
+fn sample_maze() -> Maze {
     let maze = vec![
         1, 0, 1, 1, 1,
         1, 0, 0, 1, 1,
@@ -9,11 +12,16 @@ fn test_simple_maze() {
         1, 0, 0, 1, 1,
         1, 1, 1, 1, 1
     ];
-    let maze = Maze {
+    Maze {
         data: maze.iter().map(|&i| i != 0).collect(),
         n: 5,
         m: 5,
-    };
+    }
+}
+
+#[test]
+fn test_simple_maze() {
+    let maze = sample_maze();
     let mut path = maze.solve((3, 1)).unwrap();
     // Pop, go backwards.
     assert_eq!(path.pop(), Some((0, 1)));
@@ -61,3 +69,145 @@ fn test_maze_generation() {
         assert!(path.is_some());
     }
 }
+
+#[test]
+fn test_weighted_solvers_agree_with_unweighted() {
+    let maze = sample_maze();
+    let costs = Matrix {
+        data: maze.data.iter().map(|&wall| if wall { u32::MAX } else { 1 }).collect(),
+        n: 5,
+        m: 5,
+    };
+
+    let unweighted_cost = maze.solve((3, 1)).unwrap().len() as u32 - 1;
+    let (_, dijkstra_cost) = maze.solve_weighted((3, 1), &costs).unwrap();
+    let (_, astar_cost) = maze.solve_astar((3, 1), &costs).unwrap();
+
+    assert_eq!(dijkstra_cost, unweighted_cost);
+    assert_eq!(astar_cost, dijkstra_cost);
+}
+
+#[test]
+fn test_solve_weighted_no_panic_near_border() {
+    let maze = Maze { data: vec![false; 9], n: 3, m: 3 };
+    let costs = Matrix { data: vec![1u32; 9], n: 3, m: 3 };
+
+    let (path, cost) = maze.solve_weighted((1, 1), &costs).unwrap();
+
+    assert_eq!(cost, 1);
+    assert_eq!(path.first(), Some(&(1, 1)));
+}
+
+#[test]
+fn test_reachability_no_panic_on_border_cells() {
+    let maze = Maze { data: vec![false; 9], n: 3, m: 3 };
+
+    assert!(maze.is_perfect());
+    assert!(maze.reachability().data.iter().all(|&reachable| reachable));
+}
+
+#[test]
+fn test_reachability_on_kruskal_maze() {
+    // Kruskal's only exits sit on row 0 or column 0 (src/lib.rs), the exact
+    // cells that used to panic `Neighbours::next` via `safe_add`.
+    for _ in 0..8 {
+        let maze = Maze::generate_kruskal(9, 9);
+        assert!(maze.is_perfect());
+    }
+}
+
+#[test]
+fn test_generate_kruskal_degenerate_sizes() {
+    // No interior cell/wall grid exists for these sizes; `generate` already
+    // handles them, and `generate_kruskal` used to panic picking an exit.
+    for &(n, m) in &[(1, 1), (2, 2), (2, 9), (9, 2), (1, 5), (5, 1)] {
+        let maze = Maze::generate_kruskal(n, m);
+        assert!(maze.data.iter().all(|&wall| !wall));
+    }
+}
+
+#[test]
+fn test_collect_all_keys() {
+    // The only route to the second key runs through a door that the first
+    // key unlocks.
+    let maze = RichMaze {
+        data: vec![
+            Cell::Open, Cell::Key(0), Cell::Wall,
+            Cell::Wall, Cell::Open, Cell::Door(0),
+            Cell::Wall, Cell::Wall, Cell::Key(1),
+        ],
+        n: 3,
+        m: 3,
+    };
+    let (steps, path) = maze.collect_all_keys((0, 0)).unwrap();
+    assert_eq!(steps, 4);
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(2, 2)));
+}
+
+#[test]
+fn test_collect_all_keys_starts_on_a_key() {
+    // The only key sits on `start` itself, with no neighbours to revisit it
+    // from; it must count as collected without any steps.
+    let maze = RichMaze { data: vec![Cell::Key(0)], n: 1, m: 1 };
+    let (steps, path) = maze.collect_all_keys((0, 0)).unwrap();
+    assert_eq!(steps, 0);
+    assert_eq!(path, vec![(0, 0)]);
+}
+
+#[test]
+fn test_collect_all_keys_rejects_blocked_start() {
+    let maze = RichMaze { data: vec![Cell::Wall; 9], n: 3, m: 3 };
+    assert!(maze.collect_all_keys((1, 1)).is_none());
+
+    let mut maze = maze;
+    maze[(1, 1)] = Cell::Door(0);
+    assert!(maze.collect_all_keys((1, 1)).is_none());
+}
+
+#[test]
+fn test_solve_is_generic_over_passable() {
+    // `u32::MAX` marks a wall; any other value is open.
+    let maze = Matrix {
+        data: vec![0u32, 0, u32::MAX, 0, 0, 0, u32::MAX, 0, 0],
+        n: 3,
+        m: 3,
+    };
+    let path = maze.solve((1, 1)).unwrap();
+    assert_eq!(path.first(), Some(&(1, 1)));
+}
+
+#[test]
+fn test_optimize_preserves_connectivity() {
+    // A ring: border open, full 3x3 interior wall. Scoring rewards carving
+    // exactly (2, 2), which used to produce an unreachable open island.
+    let maze = Maze {
+        data: vec![
+            false, false, false, false, false,
+            false, true, true, true, false,
+            false, true, true, true, false,
+            false, true, true, true, false,
+            false, false, false, false, false,
+        ],
+        n: 5,
+        m: 5,
+    };
+    let score = |maze: &Maze| -> i64 {
+        (1..4).flat_map(|i| (1..4).map(move |j| (i, j)))
+            .filter(|&point| !maze[point])
+            .map(|point| if point == (2, 2) { 10 } else { -10 })
+            .sum()
+    };
+
+    for seed in 0..20 {
+        let optimized = maze.optimize(seed, score, Duration::from_millis(20));
+        assert!(optimized.is_perfect());
+    }
+}
+
+#[test]
+fn test_solve_bool_mode_unchanged_after_generalization() {
+    let maze = sample_maze();
+    let path = maze.solve((3, 1)).unwrap();
+    assert_eq!(path, vec![(3, 1), (3, 2), (2, 2), (1, 2), (1, 1), (0, 1)]);
+}